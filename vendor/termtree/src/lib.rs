@@ -1,15 +1,17 @@
 use std::collections::VecDeque;
-use std::fmt::{self, Display};
+use std::fmt::{self, Display, Write as _};
+use std::io;
 use std::rc::Rc;
 
 /// a simple recursive type which is able to render its
 /// components in a tree-like format
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Tree<D: Display> {
-    root: D,
-    leaves: Vec<Tree<D>>,
+    pub root: D,
+    pub leaves: Vec<Tree<D>>,
     multiline: bool,
     glyphs: GlyphPalette,
+    max_depth: Option<usize>,
 }
 
 impl<D: Display> Tree<D> {
@@ -19,6 +21,7 @@ impl<D: Display> Tree<D> {
             leaves,
             multiline: false,
             glyphs: GlyphPalette::new(),
+            max_depth: None,
         }
     }
 
@@ -28,6 +31,7 @@ impl<D: Display> Tree<D> {
             leaves: Vec::new(),
             multiline: false,
             glyphs: GlyphPalette::new(),
+            max_depth: None,
         }
     }
 
@@ -55,10 +59,140 @@ impl<D: Display> Tree<D> {
         self
     }
 
+    /// Apply `glyphs` to this node and every descendant, so the whole tree
+    /// renders with a single consistent palette.
+    pub fn with_glyphs_recursive(mut self, glyphs: GlyphPalette) -> Self {
+        self.set_glyphs_recursive(glyphs);
+        self
+    }
+
+    /// Apply `glyphs` to this node and every descendant, so the whole tree
+    /// renders with a single consistent palette.
+    pub fn set_glyphs_recursive(&mut self, glyphs: GlyphPalette) -> &mut Self {
+        self.glyphs = glyphs;
+        for leaf in &mut self.leaves {
+            leaf.set_glyphs_recursive(glyphs);
+        }
+        self
+    }
+
     pub fn push(&mut self, leaf: Tree<D>) -> &mut Self {
         self.leaves.push(leaf);
         self
     }
+
+    /// Replace this node's leaves, converting each item into a `Tree<D>`.
+    pub fn with_leaves(mut self, leaves: impl IntoIterator<Item = impl Into<Tree<D>>>) -> Self {
+        self.leaves = leaves.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Only render down to `max_depth` levels of children below this node;
+    /// deeper subtrees are collapsed to a single overflow marker. Applies to
+    /// this node specifically, overriding any limit inherited from an
+    /// ancestor; descendants without their own limit inherit this one,
+    /// counting down one level at a time. The default is unlimited, which
+    /// preserves the current output exactly.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Only render down to `max_depth` levels of children below this node;
+    /// deeper subtrees are collapsed to a single overflow marker. Applies to
+    /// this node specifically, overriding any limit inherited from an
+    /// ancestor; descendants without their own limit inherit this one,
+    /// counting down one level at a time. The default is unlimited, which
+    /// preserves the current output exactly.
+    pub fn set_max_depth(&mut self, max_depth: usize) -> &mut Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Render this tree straight into `w`, without first collecting the
+    /// output into an owned `String` the way `to_string()` would.
+    pub fn write_to<W: io::Write>(&self, w: &mut W) -> io::Result<()> {
+        let mut adapter = IoWriteAdapter {
+            writer: w,
+            error: None,
+        };
+        match render(self, &mut adapter) {
+            Ok(()) => Ok(()),
+            Err(_) => Err(adapter
+                .error
+                .unwrap_or_else(|| io::Error::other("formatting error"))),
+        }
+    }
+
+    /// Depth-first traversal of this node and all its descendants, yielding
+    /// `(is_last_sibling, node, depth)` for each in the same order they'd be
+    /// rendered by `Display`. `self` is yielded first, with `depth` 0.
+    pub fn iter(&self) -> Iter<'_, D> {
+        Iter {
+            queue: VecDeque::from([(true, self, 0)]),
+        }
+    }
+}
+
+/// Non-consuming depth-first iterator over a [`Tree`], created by [`Tree::iter`].
+pub struct Iter<'t, D: Display> {
+    queue: VecDeque<(bool, &'t Tree<D>, usize)>,
+}
+
+impl<'t, D: Display> Iterator for Iter<'t, D> {
+    type Item = (bool, &'t Tree<D>, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (last, node, depth) = self.queue.pop_front()?;
+        for (i, leaf) in node.leaves.iter().rev().enumerate() {
+            self.queue.push_front((i == 0, leaf, depth + 1));
+        }
+        Some((last, node, depth))
+    }
+}
+
+impl<'t, D: Display> IntoIterator for &'t Tree<D> {
+    type Item = (bool, &'t Tree<D>, usize);
+    type IntoIter = Iter<'t, D>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Owning depth-first iterator over a [`Tree`], created by [`Tree::into_iter`].
+pub struct IntoIter<D: Display> {
+    queue: VecDeque<(bool, Tree<D>, usize)>,
+}
+
+impl<D: Display> Iterator for IntoIter<D> {
+    type Item = (bool, Tree<D>, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (last, mut node, depth) = self.queue.pop_front()?;
+        let leaves = std::mem::take(&mut node.leaves);
+        for (i, leaf) in leaves.into_iter().rev().enumerate() {
+            self.queue.push_front((i == 0, leaf, depth + 1));
+        }
+        Some((last, node, depth))
+    }
+}
+
+impl<D: Display> IntoIterator for Tree<D> {
+    type Item = (bool, Tree<D>, usize);
+    type IntoIter = IntoIter<D>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            queue: VecDeque::from([(true, self, 0)]),
+        }
+    }
+}
+
+impl<D: Display> From<D> for Tree<D> {
+    fn from(root: D) -> Self {
+        Tree::root(root)
+    }
 }
 
 impl<D: Display> Extend<D> for Tree<D> {
@@ -75,80 +209,188 @@ impl<D: Display> Extend<Tree<D>> for Tree<D> {
 
 impl<D: Display> Display for Tree<D> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        writeln!(f, "{}", self.root)?;
-        let mut queue = DisplauQueue::new();
-        let no_space = Rc::new(Vec::new());
-        enqueue_leaves(&mut queue, self, no_space);
-        while let Some((last, leaf, spaces)) = queue.pop_front() {
-            let mut prefix = (
+        render(self, f)
+    }
+}
+
+/// Emits the tree as structured data: `{ "value": "<root rendered via
+/// Display>", "children": [...] }`, recursively, for downstream tooling
+/// that wants the hierarchy without re-parsing the box-drawing text.
+#[cfg(feature = "serde")]
+impl<D: Display> serde::Serialize for Tree<D> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("Tree", 2)?;
+        state.serialize_field("value", &self.root.to_string())?;
+        state.serialize_field("children", &self.leaves)?;
+        state.end()
+    }
+}
+
+/// Walks `tree` in the same order the `Display` impl uses to, writing
+/// prefixes and node content to `out` as it goes. Shared by `Display::fmt`
+/// and `Tree::write_to` so the two only differ in the sink they write to.
+fn render<D: Display>(tree: &Tree<D>, out: &mut dyn fmt::Write) -> fmt::Result {
+    writeln!(out, "{}", tree.root)?;
+    let mut queue = DisplauQueue::new();
+    try_enqueue_children(&mut queue, out, tree.glyphs, tree, Rc::new(Vec::new()), None)?;
+    while let Some((last, leaf, spaces, budget)) = queue.pop_front() {
+        let prefix = (
+            if last {
+                leaf.glyphs.last_item
+            } else {
+                leaf.glyphs.middle_item
+            },
+            leaf.glyphs.item_indent,
+        );
+
+        if leaf.multiline {
+            let rest_prefix = (
                 if last {
-                    leaf.glyphs.last_item
+                    leaf.glyphs.last_skip
                 } else {
-                    leaf.glyphs.middle_item
+                    leaf.glyphs.middle_skip
                 },
-                leaf.glyphs.item_indent,
+                leaf.glyphs.skip_indent,
             );
+            debug_assert_eq!(prefix.0.chars().count(), rest_prefix.0.chars().count());
+            debug_assert_eq!(prefix.1.chars().count(), rest_prefix.1.chars().count());
 
-            if leaf.multiline {
-                let rest_prefix = (
-                    if last {
-                        leaf.glyphs.last_skip
-                    } else {
-                        leaf.glyphs.middle_skip
-                    },
-                    leaf.glyphs.skip_indent,
-                );
-                debug_assert_eq!(prefix.0.chars().count(), rest_prefix.0.chars().count());
-                debug_assert_eq!(prefix.1.chars().count(), rest_prefix.1.chars().count());
-
-                let root = leaf.root.to_string();
-                for line in root.lines() {
-                    // print single line
-                    for s in spaces.as_slice() {
-                        if *s {
-                            write!(f, "{}{}", self.glyphs.last_skip, self.glyphs.skip_indent)?;
-                        } else {
-                            write!(f, "{}{}", self.glyphs.middle_skip, self.glyphs.skip_indent)?;
-                        }
-                    }
-                    writeln!(f, "{}{}{}", prefix.0, prefix.1, line)?;
-                    prefix = rest_prefix;
-                }
-            } else {
-                // print single line
-                for s in spaces.as_slice() {
-                    if *s {
-                        write!(f, "{}{}", self.glyphs.last_skip, self.glyphs.skip_indent)?;
-                    } else {
-                        write!(f, "{}{}", self.glyphs.middle_skip, self.glyphs.skip_indent)?;
-                    }
-                }
-                writeln!(f, "{}{}{}", prefix.0, prefix.1, leaf.root)?;
-            }
+            write_ancestor_columns(out, tree.glyphs, &spaces)?;
+            write!(out, "{}{}", prefix.0, prefix.1)?;
+            let mut writer = LinePrefixWriter {
+                out: &mut *out,
+                rest_prefix,
+                ancestor_glyphs: tree.glyphs,
+                ancestor_spaces: &spaces,
+            };
+            write!(writer, "{}", leaf.root)?;
+            writeln!(out)?;
+        } else {
+            write_ancestor_columns(out, tree.glyphs, &spaces)?;
+            writeln!(out, "{}{}{}", prefix.0, prefix.1, leaf.root)?;
+        }
 
-            // recurse
-            if !leaf.leaves.is_empty() {
-                let s: &Vec<bool> = &spaces;
-                let mut child_spaces = s.clone();
-                child_spaces.push(last);
-                let child_spaces = Rc::new(child_spaces);
-                enqueue_leaves(&mut queue, leaf, child_spaces);
-            }
+        // recurse
+        let s: &Vec<bool> = &spaces;
+        let mut child_spaces = s.clone();
+        child_spaces.push(last);
+        try_enqueue_children(&mut queue, out, tree.glyphs, leaf, Rc::new(child_spaces), budget)?;
+    }
+    Ok(())
+}
+
+/// Enqueues `node`'s children for rendering, or — if `node`'s own
+/// `max_depth` (falling back to `inherited_budget` from the nearest
+/// ancestor that set one) has been exhausted — writes a single overflow
+/// marker in their place instead. `children_spaces` is the ancestor-column
+/// state `node`'s children (or the overflow marker standing in for them)
+/// should render with.
+fn try_enqueue_children<'t, D: Display>(
+    queue: &mut DisplauQueue<'t, D>,
+    out: &mut dyn fmt::Write,
+    root_glyphs: GlyphPalette,
+    node: &'t Tree<D>,
+    children_spaces: Rc<Vec<bool>>,
+    inherited_budget: Option<usize>,
+) -> fmt::Result {
+    if node.leaves.is_empty() {
+        return Ok(());
+    }
+
+    let budget = node.max_depth.or(inherited_budget);
+    if budget == Some(0) {
+        write_ancestor_columns(out, root_glyphs, &children_spaces)?;
+        writeln!(
+            out,
+            "{}{}{}",
+            node.glyphs.last_item, node.glyphs.item_indent, node.glyphs.overflow
+        )?;
+        return Ok(());
+    }
+
+    let child_budget = budget.map(|remaining| remaining - 1);
+    enqueue_leaves(queue, node, children_spaces, child_budget);
+    Ok(())
+}
+
+/// Writes the `middle_skip`/`last_skip` columns of `glyphs` that carry each
+/// ancestor's "was it the last sibling" state down through its descendants'
+/// lines.
+fn write_ancestor_columns(
+    out: &mut dyn fmt::Write,
+    glyphs: GlyphPalette,
+    spaces: &[bool],
+) -> fmt::Result {
+    for s in spaces {
+        if *s {
+            write!(out, "{}{}", glyphs.last_skip, glyphs.skip_indent)?;
+        } else {
+            write!(out, "{}{}", glyphs.middle_skip, glyphs.skip_indent)?;
+        }
+    }
+    Ok(())
+}
+
+/// Formats a multiline node's content straight into `out`, inserting the
+/// continuation prefix at each line break as it is written instead of first
+/// collecting the whole rendered body into an owned `String`.
+struct LinePrefixWriter<'a> {
+    out: &'a mut dyn fmt::Write,
+    rest_prefix: (&'static str, &'static str),
+    ancestor_glyphs: GlyphPalette,
+    ancestor_spaces: &'a [bool],
+}
+
+impl<'a> fmt::Write for LinePrefixWriter<'a> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut lines = s.split('\n');
+        if let Some(first) = lines.next() {
+            write!(self.out, "{}", first)?;
+        }
+        for line in lines {
+            writeln!(self.out)?;
+            write_ancestor_columns(self.out, self.ancestor_glyphs, self.ancestor_spaces)?;
+            write!(self.out, "{}{}{}", self.rest_prefix.0, self.rest_prefix.1, line)?;
         }
         Ok(())
     }
 }
 
-type DisplauQueue<'t, D> = VecDeque<(bool, &'t Tree<D>, Rc<Vec<bool>>)>;
+/// Adapts an [`io::Write`] sink so the shared [`render`] walk (which is
+/// written against [`fmt::Write`]) can stream straight into it.
+struct IoWriteAdapter<'w, W: io::Write> {
+    writer: &'w mut W,
+    error: Option<io::Error>,
+}
+
+impl<'w, W: io::Write> fmt::Write for IoWriteAdapter<'w, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        match self.writer.write_all(s.as_bytes()) {
+            Ok(()) => Ok(()),
+            Err(e) => {
+                self.error = Some(e);
+                Err(fmt::Error)
+            }
+        }
+    }
+}
+
+type DisplauQueue<'t, D> = VecDeque<(bool, &'t Tree<D>, Rc<Vec<bool>>, Option<usize>)>;
 
 fn enqueue_leaves<'t, D: Display>(
     queue: &mut DisplauQueue<'t, D>,
     parent: &'t Tree<D>,
     spaces: Rc<Vec<bool>>,
+    budget: Option<usize>,
 ) {
     for (i, leaf) in parent.leaves.iter().rev().enumerate() {
         let last = i == 0;
-        queue.push_front((last, leaf, spaces.clone()));
+        queue.push_front((last, leaf, spaces.clone(), budget));
     }
 }
 
@@ -161,6 +403,9 @@ pub struct GlyphPalette {
     pub middle_skip: &'static str,
     pub last_skip: &'static str,
     pub skip_indent: &'static str,
+
+    /// Marker rendered in place of a subtree collapsed by `Tree::with_max_depth`.
+    pub overflow: &'static str,
 }
 
 impl GlyphPalette {
@@ -173,6 +418,39 @@ impl GlyphPalette {
             middle_skip: "|",
             last_skip: " ",
             skip_indent: "   ",
+
+            overflow: "…",
+        }
+    }
+
+    /// Unicode box-drawing glyphs, with a proper vertical connector
+    /// (`│`) for `middle_skip` instead of a plain ASCII pipe.
+    pub const fn unicode() -> Self {
+        Self {
+            middle_item: "├",
+            last_item: "└",
+            item_indent: "── ",
+
+            middle_skip: "│",
+            last_skip: " ",
+            skip_indent: "   ",
+
+            overflow: "…",
+        }
+    }
+
+    /// Pure ASCII glyphs, for terminals without UTF-8 box-drawing support.
+    pub const fn ascii() -> Self {
+        Self {
+            middle_item: "+",
+            last_item: "`",
+            item_indent: "-- ",
+
+            middle_skip: "|",
+            last_skip: " ",
+            skip_indent: "   ",
+
+            overflow: "...",
         }
     }
 }
@@ -185,7 +463,7 @@ impl Default for GlyphPalette {
 
 #[cfg(test)]
 mod tests {
-    use super::Tree;
+    use super::{GlyphPalette, Tree};
     #[test]
     fn render_tree_root() {
         let tree = Tree::root("foo");
@@ -216,6 +494,81 @@ mod tests {
         )
     }
 
+    #[test]
+    fn with_leaves_converts_items_into_trees() {
+        let tree = Tree::root("foo").with_leaves(["bar", "baz"]);
+        assert_eq!(
+            format!("{}", tree),
+            r#"foo
+├── bar
+└── baz
+"#
+        )
+    }
+
+    #[test]
+    fn clone_produces_an_equal_rendering() {
+        let original = Tree::new("foo", vec![Tree::root("bar")]);
+        let cloned = original.clone();
+        assert_eq!(format!("{}", cloned), format!("{}", original))
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialize_emits_value_and_children() {
+        let tree = Tree::new("foo", vec![Tree::root("bar")]);
+        let json = serde_json::to_string(&tree).unwrap();
+        assert_eq!(
+            json,
+            r#"{"value":"foo","children":[{"value":"bar","children":[]}]}"#
+        )
+    }
+
+    #[test]
+    fn iter_depth_first_order() {
+        let tree = Tree::new(
+            "foo",
+            vec![Tree::new("bar", vec![Tree::root("baz")]), Tree::root("qux")],
+        );
+        let visited: Vec<_> = tree
+            .iter()
+            .map(|(last, node, depth)| (last, node.root, depth))
+            .collect();
+        assert_eq!(
+            visited,
+            vec![(true, "foo", 0), (false, "bar", 1), (true, "baz", 2), (true, "qux", 1)]
+        )
+    }
+
+    #[test]
+    fn render_tree_with_ascii_glyphs() {
+        let tree = Tree::new("foo", vec![Tree::root("bar"), Tree::root("baz")])
+            .with_glyphs_recursive(GlyphPalette::ascii());
+        assert_eq!(
+            format!("{}", tree),
+            r#"foo
++-- bar
+`-- baz
+"#
+        )
+    }
+
+    #[test]
+    fn render_tree_with_unicode_glyphs_multiline_leaf() {
+        let tree = Tree::new(
+            "foo",
+            vec![
+                Tree::root("hello\nworld").with_multiline(true),
+                Tree::root("goodbye\nworld").with_multiline(true),
+            ],
+        )
+        .with_glyphs_recursive(GlyphPalette::unicode());
+        assert_eq!(
+            format!("{}", tree),
+            "foo\n├── hello\n│   world\n└── goodbye\n    world\n"
+        )
+    }
+
     #[test]
     fn render_tree_with_multiline_leaf() {
         let tree = Tree::new(
@@ -232,6 +585,31 @@ mod tests {
 |   world
 └── goodbye
     world
+"#
+        )
+    }
+
+    #[test]
+    fn render_tree_with_max_depth_zero_collapses_children() {
+        let tree =
+            Tree::new("foo", vec![Tree::new("bar", vec![Tree::root("baz")])]).with_max_depth(0);
+        assert_eq!(
+            format!("{}", tree),
+            r#"foo
+└── …
+"#
+        )
+    }
+
+    #[test]
+    fn render_tree_respects_child_max_depth_independent_of_parent() {
+        let inner = Tree::new("bar", vec![Tree::root("baz")]).with_max_depth(0);
+        let tree = Tree::new("foo", vec![inner]);
+        assert_eq!(
+            format!("{}", tree),
+            r#"foo
+└── bar
+    └── …
 "#
         )
     }